@@ -1,3 +1,4 @@
+#[cfg(feature = "bindgen")]
 extern crate bindgen;
 
 use std::fs;
@@ -19,15 +20,30 @@ const TF_GIT_URL: &str = "https://github.com/tensorflow/tensorflow.git";
 const ANDROID_BIN_DOWNLOAD_URL: &str = formatcp!(
     "https://repo1.maven.org/maven2/org/tensorflow/tensorflow-lite/{TF_VER}/tensorflow-lite-{TF_VER}.aar"
 );
+// BLOCKING TODO: this artifact has no pinned digest yet. Run `sha256sum`
+// against the real published release (and record its byte size with
+// `wc -c`) and fill these in — until that happens, SHA-256 verification
+// and the "skip re-download if already cached" path are both a no-op for
+// this URL (download_file just trusts a successful transfer and warns),
+// so the integrity-checking goal of this change is NOT yet delivered here.
+const ANDROID_BIN_SHA256: Option<&str> = None;
+const ANDROID_BIN_SIZE: Option<u64> = None;
+
 const ANDROID_BIN_FLEX_DOWNLOAD_URL: &str = formatcp!(
     "https://repo1.maven.org/maven2/org/tensorflow/tensorflow-lite-select-tf-ops/{TF_VER}/tensorflow-lite-select-tf-ops-{TF_VER}.aar"
 );
+// BLOCKING TODO: unpinned, see ANDROID_BIN_SHA256 above.
+const ANDROID_BIN_FLEX_SHA256: Option<&str> = None;
+const ANDROID_BIN_FLEX_SIZE: Option<u64> = None;
 
 // Download URL for the iOS cannot be constucted dynamically and should be replaced manually
 // with a new release of TFLite
 const IOS_BIN_DOWNLOAD_URL: &str = formatcp!(
     "https://dl.google.com/tflite-nightly/ios/prod/tensorflow/lite/release/ios/nightly/807/20230224-035015/TensorFlowLiteC/0.0.1-nightly.20230224/TensorFlowLiteC-0.0.1-nightly.20230224.tar.gz"
 );
+// BLOCKING TODO: unpinned, see ANDROID_BIN_SHA256 above.
+const IOS_BIN_SHA256: Option<&str> = None;
+const IOS_BIN_SIZE: Option<u64> = None;
 
 fn target_os() -> String {
     env::var("CARGO_CFG_TARGET_OS").expect("Unable to get TARGET_OS")
@@ -105,6 +121,99 @@ fn prepare_tensorflow_source(tf_src_path: &Path) {
     }
 }
 
+/// Minimum Bazel version known to build the pinned TensorFlow Lite release,
+/// mirroring the check tensorflow-sys does before invoking `bazel build`.
+const MIN_BAZEL: (u32, u32, u32) = (6, 1, 0);
+
+fn check_bazel_version() {
+    let output = std::process::Command::new("bazel")
+        .arg("--version")
+        .output()
+        .unwrap_or_else(|e| {
+            panic!(
+                "Cannot execute `bazel --version`: {}. Is Bazel installed and on PATH?",
+                e
+            )
+        });
+
+    let version_str = String::from_utf8_lossy(&output.stdout);
+    let version = version_str
+        .trim()
+        .strip_prefix("bazel ")
+        .unwrap_or(version_str.trim());
+
+    let parts: Vec<u32> = version
+        .split('.')
+        .map(|p| p.parse().unwrap_or(0))
+        .collect();
+    let found = (
+        *parts.first().unwrap_or(&0),
+        *parts.get(1).unwrap_or(&0),
+        *parts.get(2).unwrap_or(&0),
+    );
+
+    if found < MIN_BAZEL {
+        panic!(
+            "Bazel {}.{}.{} or newer is required to compile TensorFlow Lite from source, found {}",
+            MIN_BAZEL.0, MIN_BAZEL.1, MIN_BAZEL.2, version
+        );
+    }
+}
+
+fn bazel_config() -> &'static str {
+    match target_os().as_str() {
+        "macos" => "macos",
+        "windows" => "windows",
+        _ => "linux",
+    }
+}
+
+fn compile_with_bazel(tf_src_path: &Path) {
+    check_bazel_version();
+
+    let mut targets = vec!["//tensorflow/lite/c:tensorflowlite_c"];
+    #[cfg(feature = "flex_delegate")]
+    targets.push("//tensorflow/lite/c:tensorflowlite_flex");
+    #[cfg(feature = "gpu_delegate")]
+    targets.push("//tensorflow/lite/delegates/gpu:libtensorflowlite_gpu_delegate.so");
+
+    let mut bazel = std::process::Command::new("bazel");
+    bazel
+        .current_dir(tf_src_path)
+        .arg("build")
+        .arg("-c")
+        .arg("opt")
+        .arg(format!("--config={}", bazel_config()))
+        .args(&targets);
+
+    println!("Starting Bazel build of {:?}", targets);
+    let start = Instant::now();
+    if !bazel.status().expect("Cannot execute `bazel build`").success() {
+        panic!("bazel build failed");
+    }
+    println!("Bazel build took {:?}", Instant::now() - start);
+
+    let bazel_bin = tf_src_path.join("bazel-bin/tensorflow/lite/c");
+
+    let libname = get_lib_name();
+    copy_or_overwrite(bazel_bin.join(&libname), lib_output_path());
+
+    #[cfg(feature = "flex_delegate")]
+    {
+        let flexname = get_flex_name();
+        copy_or_overwrite(bazel_bin.join(&flexname), flex_output_path());
+    }
+
+    #[cfg(feature = "gpu_delegate")]
+    {
+        let bazel_bin_gpu = tf_src_path.join("bazel-bin/tensorflow/lite/delegates/gpu");
+        copy_or_overwrite(
+            bazel_bin_gpu.join(get_gpu_delegate_name()),
+            gpu_delegate_output_path(),
+        );
+    }
+}
+
 fn get_lib_name() -> String {
     let ext = dll_extension();
     let lib_prefix = dll_prefix();
@@ -139,6 +248,10 @@ fn get_flex_name() -> String {
     }
 }
 
+fn get_gpu_delegate_name() -> String {
+    format!("{}tensorflowlite_gpu_delegate.{}", dll_prefix(), dll_extension())
+}
+
 fn lib_output_path() -> PathBuf {
     out_dir().join(get_lib_name())
 }
@@ -147,10 +260,140 @@ fn flex_output_path() -> PathBuf {
     out_dir().join(get_flex_name())
 }
 
+fn gpu_delegate_output_path() -> PathBuf {
+    out_dir().join(get_gpu_delegate_name())
+}
+
 fn out_dir() -> PathBuf {
     PathBuf::from(env::var("OUT_DIR").unwrap())
 }
 
+/// Strategy used to obtain `libtensorflowlite_c` (or its Android/iOS
+/// equivalents), selected via the `TFLITEC_STRATEGY` environment variable.
+#[derive(Debug, PartialEq, Eq)]
+enum LibStrategy {
+    /// Download the prebuilt binary (the existing, default behavior).
+    Download,
+    /// Link against a library already installed on the system, pointed to
+    /// by `TFLITEC_LIB_DIR` / `TFLITEC_INCLUDE_DIR`.
+    System,
+    /// Build TensorFlow Lite from source with Bazel.
+    Compile,
+}
+
+impl LibStrategy {
+    fn from_env() -> Self {
+        match env::var("TFLITEC_STRATEGY") {
+            Ok(val) => match val.as_str() {
+                "download" => LibStrategy::Download,
+                "system" => LibStrategy::System,
+                "compile" => LibStrategy::Compile,
+                other => panic!(
+                    "Unknown TFLITEC_STRATEGY '{}', expected 'download', 'system' or 'compile'",
+                    other
+                ),
+            },
+            Err(_) => LibStrategy::Download,
+        }
+    }
+}
+
+/// Links against a `libtensorflowlite_c` already installed on the system.
+/// Only reachable for Linux/macOS/Windows (`main()`'s `android`/`ios` match
+/// arms are handled unconditionally, before `LibStrategy` is even consulted)
+/// — iOS system-linking isn't in scope for this strategy.
+fn link_system_library() {
+    let lib_dir = env::var("TFLITEC_LIB_DIR")
+        .expect("TFLITEC_STRATEGY=system requires TFLITEC_LIB_DIR to be set");
+
+    println!("cargo:rustc-link-search=native={}", lib_dir);
+    println!("cargo:rustc-link-lib=dylib=tensorflowlite_c");
+
+    #[cfg(feature = "flex_delegate")]
+    println!("cargo:rustc-link-lib=dylib=tensorflowlite_flex");
+
+    #[cfg(feature = "gpu_delegate")]
+    println!("cargo:rustc-link-lib=dylib=tensorflowlite_gpu_delegate");
+}
+
+fn system_include_dir() -> Option<PathBuf> {
+    env::var("TFLITEC_INCLUDE_DIR").ok().map(PathBuf::from)
+}
+
+/// Name of the committed bindings file for the current target, following the
+/// `<arch>-<os>[-<env>][_xnnpack][_gpu_delegate][_nnapi][_coreml].rs`
+/// convention under `src/bindings/`.
+fn bindings_filename() -> String {
+    let arch = env::var("CARGO_CFG_TARGET_ARCH").expect("Unable to get TARGET_ARCH");
+    let os = target_os();
+    let env = env::var("CARGO_CFG_TARGET_ENV").unwrap_or_default();
+
+    // Every feature that changes which headers bindgen runs over must be
+    // folded in here, so a delegate feature never silently loads a
+    // pre-generated file that was built without it.
+    let mut suffix = String::new();
+    if cfg!(feature = "xnnpack") {
+        suffix.push_str("_xnnpack");
+    }
+    if cfg!(feature = "gpu_delegate") {
+        suffix.push_str("_gpu_delegate");
+    }
+    if cfg!(feature = "nnapi") {
+        suffix.push_str("_nnapi");
+    }
+    if cfg!(feature = "coreml") {
+        suffix.push_str("_coreml");
+    }
+
+    if env.is_empty() {
+        format!("{}-{}{}.rs", arch, os, suffix)
+    } else {
+        format!("{}-{}-{}{}.rs", arch, os, env, suffix)
+    }
+}
+
+fn bindings_dir() -> PathBuf {
+    PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap()).join("src/bindings")
+}
+
+/// Used when the `bindgen` feature is disabled: loads the pre-generated
+/// bindings committed for this target instead of running bindgen, and
+/// exposes their path via `cargo:rustc-env` for `lib.rs` to `include!`.
+///
+/// NOTE: this only lays the groundwork for "build with no libclang
+/// dependency" — see `src/bindings/README.md`. No `src/bindings/*.rs` files
+/// have actually been generated and committed for any target yet, so today
+/// `--no-default-features` (or any other way of turning `bindgen` off) will
+/// always hit the panic below. A maintainer with a working bindgen+libclang
+/// setup still needs to run with the `update-bindings` feature once per
+/// supported target to populate `src/bindings/` before this is usable.
+fn use_prebuilt_bindings() {
+    let bindings_path = bindings_dir().join(bindings_filename());
+
+    if !bindings_path.exists() {
+        panic!(
+            "No pre-generated bindings found at {:?} for the enabled feature set (xnnpack/\
+             gpu_delegate/nnapi/coreml). Enable the `bindgen` feature, or run with \
+             `update-bindings` to generate and commit one for this target and feature set.",
+            bindings_path
+        );
+    }
+
+    println!("cargo:rustc-env=TFLITEC_BINDGEN_FILE={}", bindings_path.display());
+}
+
+/// Copies a just-generated `bindings.rs` back into the `src/bindings/` tree
+/// so maintainers building with the `update-bindings` feature can refresh
+/// the checked-in files.
+#[cfg(feature = "bindgen")]
+fn update_committed_bindings() {
+    let generated = out_dir().join("bindings.rs");
+    let dest = bindings_dir().join(bindings_filename());
+
+    fs::create_dir_all(bindings_dir()).expect("Cannot create src/bindings directory");
+    copy_or_overwrite(&generated, &dest);
+}
+
 fn prepare_for_docsrs() {
     // Docs.rs cannot access to network, use resource files
     let library_path = out_dir().join("libtensorflowlite_c.so");
@@ -173,6 +416,7 @@ fn prepare_for_docsrs() {
     }
 }
 
+#[cfg(feature = "bindgen")]
 fn generate_binding_ios() {
     let mut builder = bindgen::Builder::default();
 
@@ -191,6 +435,14 @@ fn generate_binding_ios() {
         );
     }
 
+    if cfg!(feature = "coreml") {
+        let header_path = headers_path.join("coreml_delegate.h");
+
+        builder = builder.header(
+            header_path.to_str().unwrap()
+        );
+    }
+
     let bindings = builder
         .clang_arg(format!("-I{}", headers_path.to_str().unwrap()))
         // Tell cargo to invalidate the built crate whenever any of the
@@ -205,8 +457,61 @@ fn generate_binding_ios() {
     bindings
         .write_to_file(out_dir().join("bindings.rs"))
         .expect("Couldn't write bindings!");
+
+    #[cfg(feature = "update-bindings")]
+    update_committed_bindings();
 }
 
+#[cfg(feature = "bindgen")]
+fn generate_bindings_system(include_dir: Option<PathBuf>) {
+    // When TFLITEC_INCLUDE_DIR is not given, fall back to the headers of a
+    // typical system install under /usr/include.
+    let header_path = match &include_dir {
+        Some(dir) => dir.join("tensorflow/lite/c/c_api.h"),
+        None => PathBuf::from("/usr/include/tensorflow/lite/c/c_api.h"),
+    };
+
+    let mut builder = bindgen::Builder::default().header(header_path.to_str().unwrap());
+
+    if cfg!(feature = "xnnpack") {
+        let xnnpack_header = match &include_dir {
+            Some(dir) => dir.join("tensorflow/lite/delegates/xnnpack/xnnpack_delegate.h"),
+            None => PathBuf::from("/usr/include/tensorflow/lite/delegates/xnnpack/xnnpack_delegate.h"),
+        };
+        builder = builder.header(xnnpack_header.to_str().unwrap());
+    }
+
+    if cfg!(feature = "gpu_delegate") {
+        let gpu_header = match &include_dir {
+            Some(dir) => dir.join("tensorflow/lite/delegates/gpu/delegate.h"),
+            None => PathBuf::from("/usr/include/tensorflow/lite/delegates/gpu/delegate.h"),
+        };
+        builder = builder.header(gpu_header.to_str().unwrap());
+    }
+
+    if let Some(dir) = &include_dir {
+        builder = builder.clang_arg(format!("-I{}", dir.to_str().unwrap()));
+    }
+
+    let bindings = builder
+        // Tell cargo to invalidate the built crate whenever any of the
+        // included header files changed.
+        .parse_callbacks(Box::new(bindgen::CargoCallbacks))
+        // Finish the builder and generate the bindings.
+        .generate()
+        // Unwrap the Result and panic on failure.
+        .expect("Unable to generate bindings");
+
+    // Write the bindings to the $OUT_DIR/bindings.rs file.
+    bindings
+        .write_to_file(out_dir().join("bindings.rs"))
+        .expect("Couldn't write bindings!");
+
+    #[cfg(feature = "update-bindings")]
+    update_committed_bindings();
+}
+
+#[cfg(feature = "bindgen")]
 fn generate_bindings(tf_src_path: PathBuf) {
     let mut builder = bindgen::Builder::default().header(
         tf_src_path
@@ -222,6 +527,22 @@ fn generate_bindings(tf_src_path: PathBuf) {
                 .unwrap(),
         );
     }
+    if cfg!(feature = "gpu_delegate") {
+        builder = builder.header(
+            tf_src_path
+                .join("tensorflow/lite/delegates/gpu/delegate.h")
+                .to_str()
+                .unwrap(),
+        );
+    }
+    if cfg!(feature = "nnapi") {
+        builder = builder.header(
+            tf_src_path
+                .join("tensorflow/lite/delegates/nnapi/nnapi_delegate_c_api.h")
+                .to_str()
+                .unwrap(),
+        );
+    }
 
     let bindings = builder
         .clang_arg(format!("-I{}", tf_src_path.to_str().unwrap()))
@@ -237,12 +558,26 @@ fn generate_bindings(tf_src_path: PathBuf) {
     bindings
         .write_to_file(out_dir().join("bindings.rs"))
         .expect("Couldn't write bindings!");
+
+    #[cfg(feature = "update-bindings")]
+    update_committed_bindings();
+}
+
+/// Known-good digest of a download, used to verify integrity and to skip
+/// re-downloading a cached file that already matches.
+/// `None` fields mean the digest for this artifact hasn't been pinned yet
+/// (see the `TODO` comments above the `*_SHA256`/`*_SIZE` constants);
+/// `download_file` then trusts a successful transfer instead of verifying.
+struct ExpectedDigest {
+    sha256: Option<&'static str>,
+    size: Option<u64>,
 }
 
 fn download_ios(
     url: &str,
     save_path: &Path,
     filename: &str,
+    expected: &ExpectedDigest,
 ) {
     std::fs::create_dir_all(&save_path).unwrap();
 
@@ -251,7 +586,7 @@ fn download_ios(
 
     println!("Starting to download archive with {}...", filename);
     let start = Instant::now();
-    download_file(url, &archive_path);
+    download_file(url, &archive_path, expected);
     println!(
         "Finished downloading archive with {}, took: {:?}",
         filename,
@@ -290,13 +625,14 @@ fn download_android(
     url: &str,
     save_path: &Path,
     filename: &str,
+    expected: &ExpectedDigest,
 ) {
     std::fs::create_dir_all(&save_path).unwrap();
     let aar_path = save_path.join("android_lib");
 
     println!("Starting to download archive with {}...", filename);
     let start = Instant::now();
-    download_file(url, &aar_path);
+    download_file(url, &aar_path, expected);
     println!(
         "Finished downloading archive with {}, took: {:?}",
         filename,
@@ -328,6 +664,86 @@ fn download_android(
     file.write_all(&buff).unwrap();
 }
 
+/// Android triple used by the NDK sysroot layout, keyed by Rust's
+/// `CARGO_CFG_TARGET_ARCH`.
+fn android_ndk_triple(arch: &str) -> &'static str {
+    match arch {
+        "aarch64" => "aarch64-linux-android",
+        "armv7" => "arm-linux-androideabi",
+        "x86" => "i686-linux-android",
+        "x86_64" => "x86_64-linux-android",
+        _ => panic!("'{}' not supported", arch),
+    }
+}
+
+/// Host tag of the prebuilt LLVM toolchain shipped inside the NDK.
+fn ndk_host_tag() -> &'static str {
+    match env::consts::OS {
+        "macos" => "darwin-x86_64",
+        "windows" => "windows-x86_64",
+        _ => "linux-x86_64",
+    }
+}
+
+/// Lists the shared libraries a `.so` needs at runtime (ELF `DT_NEEDED`
+/// entries), by shelling out to `llvm-readelf -d` (falling back to
+/// `readelf`), mirroring the approach xbuild uses to discover Android
+/// runtime dependencies.
+fn needed_shared_libraries(so_path: &Path) -> Vec<String> {
+    let output = std::process::Command::new("llvm-readelf")
+        .arg("-d")
+        .arg(so_path)
+        .output()
+        .or_else(|_| std::process::Command::new("readelf").arg("-d").arg(so_path).output())
+        .unwrap_or_else(|e| panic!("Cannot run `readelf` on {:?}: {}", so_path, e));
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    stdout
+        .lines()
+        .filter(|line| line.contains("(NEEDED)"))
+        .filter_map(|line| {
+            let start = line.find('[')? + 1;
+            let end = line.find(']')?;
+            Some(line[start..end].to_string())
+        })
+        .collect()
+}
+
+/// Finds `libc++_shared.so` in the NDK sysroot for the current target arch
+/// and copies it into `out_dir()`, emitting the link-search directive so it
+/// ends up next to the other Android libraries. Also writes a manifest
+/// listing every transitive dependency the JNI library needs, so downstream
+/// packaging tooling (e.g. an APK builder) knows what to bundle.
+fn bundle_android_transitive_deps(jni_lib_path: &Path) {
+    let needed = needed_shared_libraries(jni_lib_path);
+
+    if needed.iter().any(|lib| lib == "libc++_shared.so") {
+        let ndk_home = env::var("ANDROID_NDK_HOME")
+            .expect("ANDROID_NDK_HOME must be set to locate libc++_shared.so");
+        let arch = env::var("CARGO_CFG_TARGET_ARCH").expect("Unable to get TARGET_ARCH");
+        let triple = android_ndk_triple(&arch);
+
+        let libcxx_path = PathBuf::from(&ndk_home)
+            .join("toolchains/llvm/prebuilt")
+            .join(ndk_host_tag())
+            .join("sysroot/usr/lib")
+            .join(triple)
+            .join("libc++_shared.so");
+
+        if !libcxx_path.exists() {
+            panic!("Cannot find libc++_shared.so at {:?}", libcxx_path);
+        }
+
+        copy_or_overwrite(&libcxx_path, out_dir().join("libc++_shared.so"));
+        println!("cargo:rustc-link-search=native={}", out_dir().display());
+    }
+
+    let manifest_path = out_dir().join("android_transitive_deps.txt");
+    fs::write(&manifest_path, needed.join("\n")).unwrap_or_else(|e| {
+        panic!("Cannot write {:?}: {}", manifest_path, e);
+    });
+}
+
 fn download_and_install(tf_src_path: &Path) {
     // Copy prebuilt libraries to given path
     {
@@ -338,12 +754,20 @@ fn download_and_install(tf_src_path: &Path) {
 
         match target_os().as_str() {
             "android" => {
-                download_android(ANDROID_BIN_DOWNLOAD_URL, &save_path, &libname);
+                let expected = ExpectedDigest { sha256: ANDROID_BIN_SHA256, size: ANDROID_BIN_SIZE };
+                download_android(ANDROID_BIN_DOWNLOAD_URL, &save_path, &libname, &expected);
                 #[cfg(feature = "flex_delegate")]
-                download_android(ANDROID_BIN_FLEX_DOWNLOAD_URL, &save_path, &flexname);
+                {
+                    let expected = ExpectedDigest {
+                        sha256: ANDROID_BIN_FLEX_SHA256,
+                        size: ANDROID_BIN_FLEX_SIZE,
+                    };
+                    download_android(ANDROID_BIN_FLEX_DOWNLOAD_URL, &save_path, &flexname, &expected);
+                }
             },
             "ios" => {
-                download_ios(IOS_BIN_DOWNLOAD_URL, &save_path, &libname);
+                let expected = ExpectedDigest { sha256: IOS_BIN_SHA256, size: IOS_BIN_SIZE };
+                download_ios(IOS_BIN_DOWNLOAD_URL, &save_path, &libname, &expected);
             },
             _ => {
                 panic!("Only iOS and Android are supported for now");
@@ -355,6 +779,10 @@ fn download_and_install(tf_src_path: &Path) {
 
         copy_or_overwrite(&lib_src_path, &lib_output_path);
 
+        if target_os() == "android" {
+            bundle_android_transitive_deps(&lib_output_path);
+        }
+
         #[cfg(all(android, feature = "flex_delegate"))] {
             let flex_src_path = PathBuf::from(&save_path).join(&flexname);
             let flex_output_path = flex_output_path();
@@ -364,17 +792,95 @@ fn download_and_install(tf_src_path: &Path) {
     }
 }
 
-fn download_file(url: &str, path: &Path) {
-    let mut easy = curl::easy::Easy::new();
-    let output_file = std::fs::File::create(path).unwrap();
-    let mut writer = std::io::BufWriter::new(output_file);
-    easy.url(url).unwrap();
-    easy.write_function(move |data| Ok(writer.write(data).unwrap()))
-        .unwrap();
-    easy.perform().unwrap_or_else(|e| {
-        std::fs::remove_file(path).unwrap(); // Delete corrupted or empty file
-        panic!("Error occurred while downloading from {}: {:?}", url, e);
-    });
+/// Maximum number of attempts `download_file` makes before giving up.
+const MAX_DOWNLOAD_ATTEMPTS: u32 = 4;
+
+fn sha256_hex_digest(path: &Path) -> String {
+    use sha2::{Digest, Sha256};
+
+    let mut file = fs::File::open(path).unwrap();
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher).unwrap();
+    format!("{:x}", hasher.finalize())
+}
+
+/// Whether `path` is already a verified copy of `expected`. Returns `false`
+/// when the digest hasn't been pinned yet, so an unpinned artifact is never
+/// treated as a cached/verified download and always gets (re-)fetched.
+fn matches_expected(path: &Path, expected: &ExpectedDigest) -> bool {
+    let (Some(sha256), Some(size)) = (expected.sha256, expected.size) else {
+        return false;
+    };
+
+    path.exists()
+        && fs::metadata(path).map(|m| m.len()).unwrap_or(0) == size
+        && sha256_hex_digest(path) == sha256
+}
+
+fn download_file(url: &str, path: &Path, expected: &ExpectedDigest) {
+    if expected.sha256.is_none() || expected.size.is_none() {
+        // Surfaced as a build warning (not just a source comment) so this
+        // known gap isn't silently invisible: SHA-256 verification and the
+        // "skip re-download if cached" path are both no-ops for this URL
+        // until real values are pinned, see the TODOs above ANDROID_BIN_SHA256.
+        println!(
+            "cargo:warning=No pinned SHA-256 digest for {}; downloading without integrity verification",
+            url
+        );
+    }
+
+    if matches_expected(path, expected) {
+        println!("Found cached, verified download at {:?}, skipping download", path);
+        return;
+    }
+
+    let mut last_err = None;
+    for attempt in 0..MAX_DOWNLOAD_ATTEMPTS {
+        if attempt > 0 {
+            let backoff = std::time::Duration::from_secs(2u64.pow(attempt));
+            println!(
+                "Retrying download of {} in {:?} (attempt {}/{})",
+                url,
+                backoff,
+                attempt + 1,
+                MAX_DOWNLOAD_ATTEMPTS
+            );
+            std::thread::sleep(backoff);
+        }
+
+        let mut easy = curl::easy::Easy::new();
+        let output_file = std::fs::File::create(path).unwrap();
+        let mut writer = std::io::BufWriter::new(output_file);
+        easy.url(url).unwrap();
+        easy.write_function(move |data| Ok(writer.write(data).unwrap()))
+            .unwrap();
+
+        let result = easy.perform();
+        // Dropping the handle drops the write callback, flushing its
+        // BufWriter before the file is hashed.
+        drop(easy);
+
+        match (result, expected.size.and(expected.sha256)) {
+            // No pinned digest for this artifact yet: trust a successful transfer.
+            (Ok(()), None) => return,
+            (Ok(()), Some(_)) if matches_expected(path, expected) => return,
+            (Ok(()), Some(sha256)) => {
+                last_err = Some(format!(
+                    "downloaded file did not match the expected SHA-256 digest {}",
+                    sha256
+                ));
+            }
+            (Err(e), _) => last_err = Some(e.to_string()),
+        }
+    }
+
+    let _ = std::fs::remove_file(path); // Delete corrupted or empty file
+    panic!(
+        "Error occurred while downloading from {} after {} attempts: {}",
+        url,
+        MAX_DOWNLOAD_ATTEMPTS,
+        last_err.unwrap_or_default()
+    );
 }
 
 fn main() {
@@ -385,25 +891,58 @@ fn main() {
         "android" => {
             println!("cargo:rustc-link-search=native={}", out_path.display());
             println!("cargo:rustc-link-lib=dylib=tensorflowlite_jni");
+
+            #[cfg(feature = "nnapi")]
+            println!("cargo:rustc-link-lib=dylib=neuralnetworks");
         }
         "ios" => {
             println!("cargo:rustc-link-search=framework={}", out_path.display());
             println!("cargo:rustc-link-lib=framework=TensorFlowLiteC");
             println!("cargo:rustc-link-lib=c++");
-        }
-        _ => {
-            panic!("Only iOS and Android are supported for now");
-            // println!("cargo:rustc-link-search=native={}", out_path.display());
-            // println!("cargo:rustc-link-lib=dylib=tensorflowlite_c");
 
-            // #[cfg(feature = "flex_delegate")]
-            // println!("cargo:rustc-link-lib=dylib=tensorflowlite_flex");
+            #[cfg(feature = "coreml")]
+            {
+                println!("cargo:rustc-link-lib=framework=CoreML");
+                println!("cargo:rustc-link-lib=static=TensorFlowLiteCCoreML");
+            }
         }
+        _ => match LibStrategy::from_env() {
+            LibStrategy::Download => {
+                panic!("Downloading prebuilt binaries is only supported for iOS and Android for now; use TFLITEC_STRATEGY=system or TFLITEC_STRATEGY=compile on desktop");
+            }
+            LibStrategy::System => link_system_library(),
+            LibStrategy::Compile => {
+                println!("cargo:rustc-link-search=native={}", out_path.display());
+                println!("cargo:rustc-link-lib=dylib=tensorflowlite_c");
+
+                #[cfg(feature = "flex_delegate")]
+                println!("cargo:rustc-link-lib=dylib=tensorflowlite_flex");
+
+                #[cfg(feature = "gpu_delegate")]
+                println!("cargo:rustc-link-lib=dylib=tensorflowlite_gpu_delegate");
+            }
+        },
     }
 
     if env::var("DOCS_RS") == Ok(String::from("1")) {
         // docs.rs cannot access to network, use resource files
         prepare_for_docsrs();
+    } else if os != "android" && os != "ios" && LibStrategy::from_env() == LibStrategy::System {
+        // Desktop targets linking against an already-installed library skip
+        // cloning/downloading the TensorFlow source entirely.
+        #[cfg(feature = "bindgen")]
+        generate_bindings_system(system_include_dir());
+        #[cfg(not(feature = "bindgen"))]
+        use_prebuilt_bindings();
+    } else if os != "android" && os != "ios" && LibStrategy::from_env() == LibStrategy::Compile {
+        let tf_src_path = out_path.join(format!("tensorflow_{}", TAG));
+        prepare_tensorflow_source(tf_src_path.as_path());
+        compile_with_bazel(&tf_src_path);
+
+        #[cfg(feature = "bindgen")]
+        generate_bindings(tf_src_path);
+        #[cfg(not(feature = "bindgen"))]
+        use_prebuilt_bindings();
     } else {
         let tf_src_path = out_path.join(format!("tensorflow_{}", TAG));
 
@@ -411,11 +950,17 @@ fn main() {
             prepare_tensorflow_source(tf_src_path.as_path());
             download_and_install(&tf_src_path);
 
+            #[cfg(feature = "bindgen")]
             generate_bindings(tf_src_path);
+            #[cfg(not(feature = "bindgen"))]
+            use_prebuilt_bindings();
         } else {
             download_and_install(&tf_src_path);
 
+            #[cfg(feature = "bindgen")]
             generate_binding_ios();
+            #[cfg(not(feature = "bindgen"))]
+            use_prebuilt_bindings();
         }
     }
 }